@@ -0,0 +1,34 @@
+extern crate font_index;
+
+use font_index::FontCache;
+use font_index::FontIndex;
+
+fn main() {
+    let family = std::env::args().nth(1).unwrap_or_else(|| "serif".into());
+
+    let ids: Vec<_> = match FontIndex::global().family_by_key(family.as_str()) {
+        Some(family) => family
+            .fonts()
+            .map(|font| (font.id(), font.family_name().to_string()))
+            .collect(),
+        None => {
+            println!("family {:?} not found", family);
+            return;
+        }
+    };
+
+    let mut cache = FontCache::default();
+    for (id, name) in ids {
+        if let Some(font) = cache.get(id) {
+            println!("font {:?} {:?}", name, font.attributes());
+            for feature in font.features() {
+                let tag = feature.tag().to_be_bytes();
+                println!(
+                    "    {} {}",
+                    String::from_utf8_lossy(&tag),
+                    feature.name().unwrap_or("")
+                );
+            }
+        }
+    }
+}
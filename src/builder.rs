@@ -1,9 +1,10 @@
 use super::index::*;
 use super::index_data::*;
 use super::library::FontLibrary;
-use super::system::{Os, OS};
+use super::shared_data::SharedData;
+use super::system::{FontDiscovery, SystemFontDiscovery};
 use super::types::*;
-use crate::util::{string::SmallString};
+use crate::util::string::{LowercaseString, SmallString};
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -30,7 +31,20 @@ impl Default for MmapHint {
     }
 }
 
+/// Font source requested from a library builder.
+enum PendingSource {
+    Dir(PathBuf),
+    File(PathBuf),
+    Data(SharedData),
+    SystemFonts,
+    UserFonts,
+}
+
 /// Builder for configuring a font library.
+///
+/// Font sources are recorded in the order they are added and scanned when
+/// the library is built, so all other options apply regardless of the order
+/// in which they are specified.
 #[derive(Default)]
 pub struct FontLibraryBuilder {
     inner: Inner,
@@ -38,6 +52,10 @@ pub struct FontLibraryBuilder {
     all_names: bool,
     generics: bool,
     fallbacks: bool,
+    sandbox: bool,
+    sources: Vec<PendingSource>,
+    blocked_paths: Vec<PathBuf>,
+    discovery: Option<Box<dyn FontDiscovery>>,
 }
 
 impl FontLibraryBuilder {
@@ -54,70 +72,64 @@ impl FontLibraryBuilder {
         self
     }
 
+    /// Specifies whether the library should be built without access to
+    /// the file system.
+    ///
+    /// In sandbox mode, fonts can only be added from memory with
+    /// [`add_data`](Self::add_data). Directories and files requested through
+    /// any other method are not touched; their paths are recorded instead
+    /// and reported by [`FontLibrary::blocked_paths`]. This applies to all
+    /// sources added to the builder, including those added before sandbox
+    /// mode was enabled.
+    ///
+    /// Platform configuration such as fontconfig is never read, and generic
+    /// family aliases from the discovery strategy are not applied.
+    ///
+    /// Building a sandboxed library also sandboxes the process wide default
+    /// library used by [`FontCache::default`](crate::FontCache::default) and
+    /// [`FontIndex::global`](crate::FontIndex::global) if it has not been
+    /// created yet; it then contains no fonts and reports the system font
+    /// directories as blocked paths. Sandboxed embedders should create
+    /// caches from their library with [`FontCache::new`](crate::FontCache::new).
+    pub fn sandbox(&mut self, yes: bool) -> &mut Self {
+        self.sandbox = yes;
+        self
+    }
+
     /// Adds fonts from the specified directory to the library.
     pub fn add_dir(&mut self, path: impl AsRef<Path>) -> &mut Self {
-        self.scanner.scan_dir(path, self.all_names, &mut self.inner);
+        self.sources.push(PendingSource::Dir(path.as_ref().into()));
         self
     }
 
     /// Adds a font file to the library.
     pub fn add_file(&mut self, path: impl AsRef<Path>) -> &mut Self {
-        self.scanner
-            .scan_file(path, self.all_names, &mut self.inner);
+        self.sources.push(PendingSource::File(path.as_ref().into()));
+        self
+    }
+
+    /// Adds fonts from the specified in-memory font data to the library.
+    pub fn add_data(&mut self, data: SharedData) -> &mut Self {
+        self.sources.push(PendingSource::Data(data));
+        self
+    }
+
+    /// Specifies the strategy used to locate system and user fonts. The
+    /// default uses the standard locations for the current platform.
+    pub fn discovery(&mut self, discovery: impl FontDiscovery + 'static) -> &mut Self {
+        self.discovery = Some(Box::new(discovery));
         self
     }
 
     /// Adds system fonts to the library.
     pub fn add_system_fonts(&mut self) -> &mut Self {
-        match OS {
-            Os::Windows => {
-                if let Some(mut windir) = std::env::var_os("SYSTEMROOT") {
-                    windir.push("\\Fonts\\");
-                    self.add_dir(windir);
-                } else {
-                    self.add_dir("C:\\Windows\\Fonts\\");
-                }
-            }
-            Os::MacOs => {
-                self.add_dir("/System/Library/Fonts/");
-                self.add_dir("/Library/Fonts/");
-            }
-            Os::Ios => {
-                self.add_dir("/System/Library/Fonts/");
-                self.add_dir("/Library/Fonts/");
-            }
-            Os::Android => {
-                self.add_dir("/system/fonts/");
-            }
-            Os::Unix => {
-                self.add_dir("/usr/share/fonts/");
-                self.add_dir("/usr/local/share/fonts/");
-            }
-            Os::Other => {}
-        }
+        self.sources.push(PendingSource::SystemFonts);
         self
     }
 
     /// Adds user fonts to the library.
     pub fn add_user_fonts(&mut self) -> &mut Self {
-        match OS {
-            Os::Windows => {}
-            Os::MacOs => {
-                if let Some(mut homedir) = std::env::var_os("HOME") {
-                    homedir.push("/Library/Fonts/");
-                    self.add_dir(&homedir);
-                }
-            }
-            Os::Ios => {}
-            Os::Android => {}
-            Os::Unix => {
-                if let Some(mut homedir) = std::env::var_os("HOME") {
-                    homedir.push("/.local/share/fonts/");
-                    self.add_dir(&homedir);
-                }
-            }
-            Os::Other => {}
-        }
+        self.sources.push(PendingSource::UserFonts);
         self
     }
 
@@ -137,21 +149,45 @@ impl FontLibraryBuilder {
 
     /// Builds a library for the current configuration.
     pub fn build(&mut self) -> FontLibrary {
-        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
-        let fontconfig = load_fontconfig();
-        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
-        {
-            let home = std::env::var("HOME");
-            fontconfig.dirs.iter().for_each(|dir| {
-                if dir.path.starts_with("~") {
-                    if let Ok(ref home) = home {
-                        let path = Path::new(home).join(dir.path.strip_prefix("~").unwrap());
-                        self.add_dir(path);
+        let mut sources = Vec::new();
+        core::mem::swap(&mut sources, &mut self.sources);
+        let discovery = self
+            .discovery
+            .get_or_insert_with(|| Box::new(SystemFontDiscovery::default()));
+        // Platform configuration is read at most once per build and never
+        // in sandbox mode.
+        let uses_discovery = sources.iter().any(|source| {
+            matches!(source, PendingSource::SystemFonts | PendingSource::UserFonts)
+        });
+        if !self.sandbox && (self.generics || uses_discovery) {
+            discovery.load_config();
+        }
+        let system_dirs = discovery.system_font_dirs();
+        let user_dirs = discovery.user_font_dirs();
+        let generic_families = if self.generics && !self.sandbox {
+            discovery.generic_families()
+        } else {
+            Vec::new()
+        };
+        for source in sources {
+            match source {
+                PendingSource::Dir(path) => self.scan_dir(path),
+                PendingSource::File(path) => self.scan_file(path),
+                PendingSource::Data(data) => {
+                    self.scanner
+                        .scan_memory(data, self.all_names, &mut self.inner);
+                }
+                PendingSource::SystemFonts => {
+                    for dir in &system_dirs {
+                        self.scan_dir(dir);
                     }
-                } else {
-                    self.add_dir(dir.clone().path);
-                };
-            });
+                }
+                PendingSource::UserFonts => {
+                    for dir in &user_dirs {
+                        self.scan_dir(dir);
+                    }
+                }
+            }
         }
         let mut index = StaticIndex::default();
         core::mem::swap(&mut index, &mut self.inner.index);
@@ -167,43 +203,42 @@ impl FontLibraryBuilder {
             index.setup_default_fallbacks();
         }
 
-        #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
-        {
-            use fontconfig_parser::Alias;
-
-            if self.generics {
-                fontconfig.aliases.iter().for_each(
-                    |Alias {
-                         alias,
-                         default,
-                         prefer,
-                         accept,
-                     }| {
-                        let names = [&prefer[..], &accept[..], &default[..]].concat();
-                        let find_family = |families: Vec<String>| {
-                            for family in families {
-                                if let Some(id) = index.base.family_map.get(family.as_str()) {
-                                    return Some(*id);
-                                }
-                            }
-                            None
-                        };
-                        if let Some(generic_family) = GenericFamily::parse(alias) {
-                            if let Some(id) = find_family(names.clone()) {
-                                index.generic[generic_family as usize] = Some(id);
-                            };
-                        }
-                    },
-                )
+        for (generic, names) in generic_families {
+            let id = names.iter().find_map(|name| {
+                let mut s = LowercaseString::new();
+                index.base.family_map.get(s.get(name)?).copied()
+            });
+            if let Some(id) = id {
+                index.generic[generic as usize] = Some(id);
             }
         }
 
-        FontLibrary::new(index)
+        let mut blocked_paths = Vec::new();
+        core::mem::swap(&mut blocked_paths, &mut self.blocked_paths);
+        FontLibrary::new(index, self.sandbox, blocked_paths)
+    }
+
+    fn scan_dir(&mut self, path: impl AsRef<Path>) {
+        if self.sandbox {
+            self.blocked_paths.push(path.as_ref().into());
+            return;
+        }
+        self.scanner.scan_dir(path, self.all_names, &mut self.inner);
+    }
+
+    fn scan_file(&mut self, path: impl AsRef<Path>) {
+        if self.sandbox {
+            self.blocked_paths.push(path.as_ref().into());
+            return;
+        }
+        self.scanner
+            .scan_file(path, self.all_names, &mut self.inner);
     }
 }
 
 struct Inner {
     path: PathBuf,
+    memory: Option<SharedData>,
     mmap: bool,
     timestamp: SystemTime,
     source: SourceId,
@@ -223,6 +258,7 @@ impl Inner {
     fn new() -> Self {
         Self {
             path: PathBuf::new(),
+            memory: None,
             mmap: false,
             timestamp: SystemTime::UNIX_EPOCH,
             source: SourceId(0),
@@ -242,12 +278,19 @@ impl ScannerSink for Inner {
             MmapHint::Threshold(value) => (value as u64) < size,
         };
         self.path = path;
+        self.memory = None;
         self.mmap = mmap;
         self.timestamp = timestamp;
         self.source = SourceId(self.index.base.sources.len() as u32);
         self.file_added = false;
     }
 
+    fn enter_memory(&mut self, data: SharedData) {
+        self.memory = Some(data);
+        self.source = SourceId(self.index.base.sources.len() as u32);
+        self.file_added = false;
+    }
+
     fn add_font(&mut self, font: &FontInfo) {
         self.lowercase_name.clear();
         self.lowercase_name
@@ -277,16 +320,22 @@ impl ScannerSink for Inner {
             };
         if !self.file_added {
             self.file_added = true;
-            let mut path2 = PathBuf::new();
-            core::mem::swap(&mut path2, &mut self.path);
+            let kind = match self.memory.take() {
+                Some(data) => SourceKind::Memory(data),
+                None => {
+                    let mut path2 = PathBuf::new();
+                    core::mem::swap(&mut path2, &mut self.path);
+                    SourceKind::File(FileData {
+                        path: path2.into(),
+                        mmap: self.mmap,
+                        timestamp: self.timestamp,
+                        status: RwLock::new(FileDataStatus::Empty),
+                    })
+                }
+            };
             index.base.sources.push(SourceData {
                 id: self.source,
-                kind: SourceKind::File(FileData {
-                    path: path2.into(),
-                    mmap: self.mmap,
-                    timestamp: self.timestamp,
-                    status: RwLock::new(FileDataStatus::Empty),
-                }),
+                kind,
             });
         }
         let font_id = FontId(index.base.fonts.len() as u32);
@@ -342,6 +391,7 @@ impl FontInfo {
 
 pub trait ScannerSink {
     fn enter_file(&mut self, path: PathBuf, timestamp: SystemTime, size: u64);
+    fn enter_memory(&mut self, data: SharedData);
     fn add_font(&mut self, font: &FontInfo);
 }
 
@@ -376,6 +426,16 @@ impl Scanner {
         self.scan_data(&*data, all_names, |f| sink.add_font(f))
     }
 
+    pub fn scan_memory(
+        &mut self,
+        data: SharedData,
+        all_names: bool,
+        sink: &mut impl ScannerSink,
+    ) -> Option<()> {
+        sink.enter_memory(data.clone());
+        self.scan_data(&data, all_names, |f| sink.add_font(f))
+    }
+
     pub fn scan_data(
         &mut self,
         data: &[u8],
@@ -522,36 +582,3 @@ impl Scanner {
         Some(())
     }
 }
-
-#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
-fn load_fontconfig() -> fontconfig_parser::FontConfig {
-    let mut fontconfig = fontconfig_parser::FontConfig::default();
-    let home = std::env::var("HOME");
-
-    if let Ok(ref config_file) = std::env::var("FONTCONFIG_FILE") {
-        let _ = fontconfig.merge_config(Path::new(config_file));
-    } else {
-        let xdg_config_home = if let Ok(val) = std::env::var("XDG_CONFIG_HOME") {
-            Some(val.into())
-        } else if let Ok(ref home) = home {
-            // according to https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html
-            // $XDG_CONFIG_HOME should default to $HOME/.config if not set
-            Some(Path::new(home).join(".config"))
-        } else {
-            None
-        };
-
-        let read_global = match xdg_config_home {
-            Some(p) => fontconfig
-                .merge_config(&p.join("fontconfig/fonts.conf"))
-                .is_err(),
-            None => true,
-        };
-
-        if read_global {
-            let _ = fontconfig.merge_config(Path::new("/etc/fonts/local.conf"));
-        }
-        let _ = fontconfig.merge_config(Path::new("/etc/fonts/fonts.conf"));
-    }
-    fontconfig
-}
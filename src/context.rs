@@ -3,7 +3,7 @@ use super::index::*;
 use super::library::FontLibrary;
 use super::types::{FamilyId, FamilyKey, FontId, FontKey, SourceId};
 use super::{shared_data::SharedData, Font};
-use crate::util::fxhash::FxHashMap;
+use crate::util::fxhash::{FxHashMap, FxHashSet};
 use std::sync::Arc;
 use swash::proxy::CharmapProxy;
 use swash::text::{
@@ -24,20 +24,19 @@ pub struct FontContext {
     library: FontLibrary,
     fonts: FontCache,
     groups: GroupCache,
+    missing: Vec<char>,
+    missing_set: FxHashSet<char>,
 }
 
 impl FontContext {
     pub fn new(library: FontLibrary) -> Self {
-        let index = library.inner.index.read().unwrap().clone();
-        let fonts = FontCache {
-            index,
-            sources: FxHashMap::default(),
-            epoch: 0,
-        };
+        let fonts = FontCache::new(&library);
         Self {
             library,
             fonts,
             groups: GroupCache::default(),
+            missing: Vec::new(),
+            missing_set: FxHashSet::default(),
         }
     }
 
@@ -57,6 +56,19 @@ impl FontContext {
         self.groups.get(&self.fonts, families, key, attrs)
     }
 
+    /// Returns the characters that were not supported by any font in the
+    /// selected group or the fallback chain. These accumulate across layout
+    /// sessions until cleared with [`clear_missing_chars`](Self::clear_missing_chars).
+    pub fn missing_chars(&self) -> &[char] {
+        &self.missing
+    }
+
+    /// Clears the list of missing characters.
+    pub fn clear_missing_chars(&mut self) {
+        self.missing.clear();
+        self.missing_set.clear();
+    }
+
     /// Selects a font group for subsequent cluster mapping operations.
     pub fn select_group(&mut self, descriptor: FontGroupId) {
         self.groups.select(descriptor);
@@ -136,22 +148,48 @@ impl FontContext {
                 None => continue,
             }
         }
+        // No font provides a complete mapping, so record the characters that
+        // were left without a glyph.
+        for ch in cluster.mapped_chars() {
+            if ch.glyph_id == 0
+                && ch.contributes_to_shaping
+                && !ch.ignorable
+                && self.missing_set.insert(ch.ch)
+            {
+                self.missing.push(ch.ch);
+            }
+        }
         best
     }
 }
 
 pub struct FontCache {
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub index: Arc<StaticIndex>,
     sources: FxHashMap<SourceId, Option<(SharedData, Epoch)>>,
+    charmaps: FxHashMap<FontId, CharmapProxy>,
     epoch: Epoch,
 }
 
 impl FontCache {
+    /// Creates a new font cache for the specified library.
+    pub fn new(library: &FontLibrary) -> Self {
+        let index = library.inner.index.read().unwrap().clone();
+        FontCache {
+            index,
+            sources: FxHashMap::default(),
+            charmaps: FxHashMap::default(),
+            epoch: 0,
+        }
+    }
+
     pub fn default() -> Self {
         let index = StaticIndex::global().clone();
         FontCache {
             index,
             sources: FxHashMap::default(),
+            charmaps: FxHashMap::default(),
             epoch: 0,
         }
     }
@@ -225,6 +263,17 @@ impl FontCache {
             }
         }
     }
+
+    /// Returns the character map proxy for the specified font, which must
+    /// be the font loaded for `id`. The proxy is computed on first use and
+    /// retained for the lifetime of the cache so that font groups rebuilt in
+    /// each layout session don't need to parse the character map again.
+    pub(crate) fn charmap_proxy(&mut self, id: FontId, font: &Font) -> CharmapProxy {
+        *self
+            .charmaps
+            .entry(id)
+            .or_insert_with(|| CharmapProxy::from_font(&font.as_ref()))
+    }
 }
 
 /// Internal cache of font groups.
@@ -567,7 +616,7 @@ impl CachedFont {
                         return None;
                     }
                 };
-                self.charmap = CharmapProxy::from_font(&font.as_ref());
+                self.charmap = fonts.charmap_proxy(self.id, &font);
                 self.font = Some(font);
                 self.font.as_ref().unwrap()
             }
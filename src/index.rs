@@ -26,10 +26,20 @@ pub struct BaseIndex {
 }
 
 pub struct StaticIndex {
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub base: BaseIndex,
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub families: Vec<FamilyData>,
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub script_map: FxHashMap<Script, Fallbacks>,
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub cjk: [Fallbacks; 5],
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub generic: [Option<FamilyId>; 13],
 }
 
@@ -47,6 +57,8 @@ impl Default for StaticIndex {
 }
 
 impl StaticIndex {
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub fn setup_default_fallbacks(&mut self) {
         use super::system::*;
         use Cjk::*;
@@ -184,6 +196,8 @@ impl StaticIndex {
         }
     }
 
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub fn setup_default_generic(&mut self) {
         use super::system::*;
         use GenericFamily::*;
@@ -447,6 +461,8 @@ impl<'a> FontEntry<'a> {
 /// Source entry in a library.
 #[derive(Copy, Clone)]
 pub struct SourceEntry<'a> {
+    #[doc(hidden)]
+    #[deprecated(note = "internal implementation detail; will be removed in 0.2")]
     pub index: &'a BaseIndex,
     data: &'a SourceData,
 }
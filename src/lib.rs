@@ -5,10 +5,14 @@ Font management.
 pub mod prelude;
 mod util;
 
+// The deprecated public index internals are still used within the crate.
+#[allow(deprecated)]
 mod builder;
+#[allow(deprecated)]
 pub mod context;
 mod fallback;
 mod family;
+#[allow(deprecated)]
 mod index;
 mod index_data;
 mod library;
@@ -19,7 +23,7 @@ mod types;
 pub(crate) mod internal {
     pub use super::context::{FontContext, FontGroupId};
 }
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 pub use builder::{FontLibraryBuilder, MmapHint};
 pub use context::{FontCache, FontContext};
@@ -28,6 +32,7 @@ pub use index::{FamilyEntry, FontEntry, SourceEntry, StaticIndex as FontIndex};
 pub use index_data::SourceKind;
 pub use library::FontLibrary;
 pub use shared_data::SharedData;
+pub use system::{FontDiscovery, SystemFontDiscovery};
 pub use types::{FamilyId, FamilyKey, FontId, FontKey, GenericFamily, SourceId};
 
 use swash::{CacheKey, iter::*, *};
@@ -138,11 +143,10 @@ impl<'a> From<&'a Font> for FontRef<'a> {
     }
 }
 
-static FONT_LIBRARY: OnceLock<FontLibrary> = OnceLock::new();
-
 impl FontIndex {
+    /// Returns the index of the [global](FontLibrary::global) library.
     pub fn global() -> Arc<FontIndex> {
-        let library = FONT_LIBRARY.get_or_init(|| FontLibrary::default());
+        let library = FontLibrary::global();
 
         library.inner.index.read().unwrap().clone()
     }
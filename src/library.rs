@@ -1,6 +1,14 @@
 use super::builder::{FontLibraryBuilder, MmapHint};
 use super::index::StaticIndex;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+static GLOBAL_LIBRARY: OnceLock<FontLibrary> = OnceLock::new();
+
+/// Set once any library is built in sandbox mode. Default libraries built
+/// afterwards are sandboxed as well.
+static PROCESS_SANDBOXED: AtomicBool = AtomicBool::new(false);
 
 /// Indexed collection of fonts used during layout for font selection and
 /// fallback.
@@ -15,18 +23,48 @@ impl FontLibrary {
         FontLibraryBuilder::default()
     }
 
-    pub(super) fn new(index: StaticIndex) -> Self {
+    /// Returns the library shared by the process, building the default
+    /// library on first use.
+    ///
+    /// Once a library has been built in sandbox mode, the default library
+    /// is built in sandbox mode as well and reports the system and user font
+    /// directories through [`blocked_paths`](Self::blocked_paths) instead of
+    /// scanning them.
+    pub fn global() -> &'static FontLibrary {
+        GLOBAL_LIBRARY.get_or_init(FontLibrary::default)
+    }
+
+    pub(super) fn new(index: StaticIndex, sandbox: bool, blocked_paths: Vec<PathBuf>) -> Self {
+        if sandbox {
+            PROCESS_SANDBOXED.store(true, Ordering::Relaxed);
+        }
         Self {
             inner: Arc::new(Inner {
                 index: RwLock::new(Arc::new(index)),
+                sandbox,
+                blocked_paths,
             }),
         }
     }
+
+    /// Returns true if the library was built in sandbox mode.
+    pub fn is_sandboxed(&self) -> bool {
+        self.inner.sandbox
+    }
+
+    /// Returns the paths that were requested while building in sandbox mode
+    /// and were not accessed.
+    pub fn blocked_paths(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.inner.blocked_paths.iter().map(|p| p.as_path())
+    }
 }
 
 impl Default for FontLibrary {
+    /// Builds a library with the system and user fonts. This is sandboxed
+    /// if any library was previously built in sandbox mode.
     fn default() -> Self {
         Self::builder()
+            .sandbox(PROCESS_SANDBOXED.load(Ordering::Relaxed))
             .mmap(MmapHint::Threshold(1024 * 1024))
             .add_system_fonts()
             .add_user_fonts()
@@ -38,4 +76,6 @@ impl Default for FontLibrary {
 
 pub struct Inner {
     pub index: RwLock<Arc<StaticIndex>>,
+    pub sandbox: bool,
+    pub blocked_paths: Vec<PathBuf>,
 }
@@ -1,4 +1,21 @@
 //! Commonly used font types.
+//!
+//! The contents of the prelude are versioned. The unversioned `prelude`
+//! always re-exports the latest version, while users that want to pin the
+//! surface they build against can import from a specific version such as
+//! [`v1`]. Items are only removed from a version after being marked
+//! `#[deprecated]` for at least one minor release, and releases are checked
+//! with `cargo semver-checks` as described in the README.
 
-pub use super::Font;
-pub use swash::{Stretch, Style, Setting, Weight};
+pub use v1::*;
+
+/// Version 1 of the prelude.
+pub mod v1 {
+    pub use crate::context::{FontCache, FontContext};
+    pub use crate::{
+        FamilyId, FamilyKey, FamilyList, Font, FontDiscovery, FontId, FontIndex, FontKey,
+        FontLibrary, FontLibraryBuilder, GenericFamily, MmapHint, SharedData,
+        SystemFontDiscovery,
+    };
+    pub use swash::{Attributes, Setting, Stretch, Style, Weight};
+}
@@ -1,6 +1,9 @@
 //! Support for detecting OS specific font paths and selecting appropriate
 //! fallbacks.
 
+use super::types::GenericFamily;
+use std::path::PathBuf;
+
 #[allow(dead_code)]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Os {
@@ -33,3 +36,158 @@ pub const OS: Os = Os::Android;
 #[cfg(not(any(unix, windows)))]
 pub const OS: Os = Os::Other;
 
+/// Source of platform specific font locations.
+///
+/// The library builder uses this to locate system and user fonts, allowing
+/// embedders to supply their own discovery strategy or a mock in tests.
+/// Any platform configuration that determines the locations is read by
+/// [`load_config`](Self::load_config), which the builder calls once per
+/// build and never in sandbox mode. The remaining methods only report the
+/// locations and should not access the file system.
+pub trait FontDiscovery: Send + Sync {
+    /// Loads platform configuration that determines the reported locations.
+    fn load_config(&mut self) {}
+
+    /// Returns the directories that contain system fonts.
+    fn system_font_dirs(&self) -> Vec<PathBuf>;
+
+    /// Returns the directories that contain fonts installed by the current
+    /// user.
+    fn user_font_dirs(&self) -> Vec<PathBuf>;
+
+    /// Returns lists of preferred family names for generic families, in
+    /// order of preference. These are applied over the default generic
+    /// families when they are mapped, with later entries for the same
+    /// generic family taking precedence.
+    fn generic_families(&self) -> Vec<(GenericFamily, Vec<String>)> {
+        Vec::new()
+    }
+}
+
+/// Font discovery using the default locations for the current platform.
+///
+/// On Unix platforms that use fontconfig, loading the configuration adds
+/// the configured font directories and generic family aliases. Until then,
+/// only the standard locations are reported.
+#[derive(Clone, Default, Debug)]
+pub struct SystemFontDiscovery {
+    config_dirs: Vec<PathBuf>,
+    config_generic_families: Vec<(GenericFamily, Vec<String>)>,
+}
+
+impl FontDiscovery for SystemFontDiscovery {
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+    fn load_config(&mut self) {
+        let fontconfig = load_fontconfig();
+        let home = std::env::var("HOME");
+        self.config_dirs.clear();
+        for dir in fontconfig.dirs {
+            if dir.path.starts_with("~") {
+                if let Ok(ref home) = home {
+                    let path = std::path::Path::new(home);
+                    self.config_dirs
+                        .push(path.join(dir.path.strip_prefix("~").unwrap()));
+                }
+            } else {
+                self.config_dirs.push(dir.path);
+            }
+        }
+        self.config_generic_families = fontconfig
+            .aliases
+            .into_iter()
+            .filter_map(|alias| {
+                let generic = GenericFamily::parse(&alias.alias)?;
+                Some((generic, [alias.prefer, alias.accept, alias.default].concat()))
+            })
+            .collect();
+    }
+
+    fn system_font_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Self::standard_system_font_dirs();
+        dirs.extend_from_slice(&self.config_dirs);
+        dirs
+    }
+
+    fn user_font_dirs(&self) -> Vec<PathBuf> {
+        Self::standard_user_font_dirs()
+    }
+
+    fn generic_families(&self) -> Vec<(GenericFamily, Vec<String>)> {
+        self.config_generic_families.clone()
+    }
+}
+
+impl SystemFontDiscovery {
+    fn standard_system_font_dirs() -> Vec<PathBuf> {
+        match OS {
+            Os::Windows => {
+                if let Some(mut windir) = std::env::var_os("SYSTEMROOT") {
+                    windir.push("\\Fonts\\");
+                    vec![windir.into()]
+                } else {
+                    vec!["C:\\Windows\\Fonts\\".into()]
+                }
+            }
+            Os::MacOs | Os::Ios => vec![
+                "/System/Library/Fonts/".into(),
+                "/Library/Fonts/".into(),
+            ],
+            Os::Android => vec!["/system/fonts/".into()],
+            Os::Unix => vec![
+                "/usr/share/fonts/".into(),
+                "/usr/local/share/fonts/".into(),
+            ],
+            Os::Other => vec![],
+        }
+    }
+
+    fn standard_user_font_dirs() -> Vec<PathBuf> {
+        let subdir = match OS {
+            Os::MacOs => "/Library/Fonts/",
+            Os::Unix => "/.local/share/fonts/",
+            _ => return vec![],
+        };
+        match std::env::var_os("HOME") {
+            Some(mut homedir) => {
+                homedir.push(subdir);
+                vec![homedir.into()]
+            }
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn load_fontconfig() -> fontconfig_parser::FontConfig {
+    use std::path::Path;
+
+    let mut fontconfig = fontconfig_parser::FontConfig::default();
+    let home = std::env::var("HOME");
+
+    if let Ok(ref config_file) = std::env::var("FONTCONFIG_FILE") {
+        let _ = fontconfig.merge_config(Path::new(config_file));
+    } else {
+        let xdg_config_home = if let Ok(val) = std::env::var("XDG_CONFIG_HOME") {
+            Some(val.into())
+        } else if let Ok(ref home) = home {
+            // according to https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html
+            // $XDG_CONFIG_HOME should default to $HOME/.config if not set
+            Some(Path::new(home).join(".config"))
+        } else {
+            None
+        };
+
+        let read_global = match xdg_config_home {
+            Some(p) => fontconfig
+                .merge_config(&p.join("fontconfig/fonts.conf"))
+                .is_err(),
+            None => true,
+        };
+
+        if read_global {
+            let _ = fontconfig.merge_config(Path::new("/etc/fonts/local.conf"));
+        }
+        let _ = fontconfig.merge_config(Path::new("/etc/fonts/fonts.conf"));
+    }
+    fontconfig
+}
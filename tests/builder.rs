@@ -0,0 +1,134 @@
+use font_index::{FontCache, FontDiscovery, FontLibrary, FontLibraryBuilder, GenericFamily};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn sandbox_blocks_sources_added_before_it_is_enabled() {
+    let library = FontLibrary::builder()
+        .add_dir("/usr/share/fonts/")
+        .add_file("/usr/share/fonts/test.ttf")
+        .sandbox(true)
+        .add_dir("/usr/local/share/fonts/")
+        .build();
+    assert!(library.is_sandboxed());
+    assert_eq!(
+        library.blocked_paths().collect::<Vec<_>>(),
+        [
+            Path::new("/usr/share/fonts/"),
+            Path::new("/usr/share/fonts/test.ttf"),
+            Path::new("/usr/local/share/fonts/"),
+        ]
+    );
+    assert!(FontLibrary::builder()
+        .sandbox(true)
+        .build()
+        .blocked_paths()
+        .next()
+        .is_none());
+}
+
+struct MockDiscovery;
+
+impl FontDiscovery for MockDiscovery {
+    fn system_font_dirs(&self) -> Vec<PathBuf> {
+        vec!["/mock/system/".into()]
+    }
+
+    fn user_font_dirs(&self) -> Vec<PathBuf> {
+        vec!["/mock/user/".into()]
+    }
+}
+
+struct EmptyDiscovery;
+
+impl FontDiscovery for EmptyDiscovery {
+    fn system_font_dirs(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    fn user_font_dirs(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+#[derive(Default)]
+struct CountingDiscovery {
+    loads: Arc<AtomicUsize>,
+}
+
+impl FontDiscovery for CountingDiscovery {
+    fn load_config(&mut self) {
+        self.loads.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn system_font_dirs(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+
+    fn user_font_dirs(&self) -> Vec<PathBuf> {
+        vec![]
+    }
+}
+
+#[test]
+fn discovery_config_is_loaded_once_per_build() {
+    let discovery = CountingDiscovery::default();
+    let loads = discovery.loads.clone();
+    let mut builder = FontLibrary::builder();
+    builder
+        .discovery(discovery)
+        .add_system_fonts()
+        .add_user_fonts()
+        .map_generic_families(true);
+    builder.build();
+    assert_eq!(loads.load(Ordering::SeqCst), 1);
+    builder.add_system_fonts().build();
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn sandbox_does_not_load_discovery_config() {
+    let discovery = CountingDiscovery::default();
+    let loads = discovery.loads.clone();
+    FontLibrary::builder()
+        .sandbox(true)
+        .discovery(discovery)
+        .add_system_fonts()
+        .map_generic_families(true)
+        .build();
+    assert_eq!(loads.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn discovery_provides_system_and_user_font_dirs() {
+    let library = FontLibrary::builder()
+        .sandbox(true)
+        .add_user_fonts()
+        .discovery(MockDiscovery)
+        .add_system_fonts()
+        .build();
+    assert_eq!(
+        library.blocked_paths().collect::<Vec<_>>(),
+        [Path::new("/mock/user/"), Path::new("/mock/system/")]
+    );
+}
+
+#[test]
+fn empty_discovery_finds_no_fonts() {
+    let library = FontLibrary::builder()
+        .discovery(EmptyDiscovery)
+        .add_system_fonts()
+        .add_user_fonts()
+        .map_generic_families(true)
+        .build();
+    let cache = FontCache::new(&library);
+    assert!(cache.query(GenericFamily::SansSerif, ()).is_none());
+    assert!(cache.query(GenericFamily::Serif, ()).is_none());
+}
+
+#[test]
+fn builder_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<FontLibraryBuilder>();
+}
@@ -0,0 +1,34 @@
+use font_index::{FontContext, FontLibrary};
+use swash::text::cluster::{CharCluster, CharInfo, Parser, Token};
+use swash::text::{Codepoint, Script};
+use swash::{Attributes, Synthesis};
+
+fn map_text(context: &mut FontContext, text: &str) {
+    let tokens = text.char_indices().map(|(offset, ch)| Token {
+        ch,
+        offset: offset as u32,
+        len: ch.len_utf8() as u8,
+        info: CharInfo::from(ch.properties()),
+        data: 0,
+    });
+    let mut parser = Parser::new(Script::Han, tokens);
+    let mut cluster = CharCluster::new();
+    while parser.next(&mut cluster) {
+        context.map_cluster(&mut cluster, &mut Synthesis::default());
+    }
+}
+
+#[test]
+fn missing_chars_are_recorded_once_in_order() {
+    let library = FontLibrary::builder().sandbox(true).build();
+    let mut context = FontContext::new(library);
+    let group = context.register_group("sans-serif", 0, Attributes::default());
+    context.select_group(group);
+    map_text(&mut context, "中中😀");
+    map_text(&mut context, "😀中");
+    assert_eq!(context.missing_chars(), ['中', '😀']);
+    context.clear_missing_chars();
+    assert!(context.missing_chars().is_empty());
+    map_text(&mut context, "😀");
+    assert_eq!(context.missing_chars(), ['😀']);
+}
@@ -0,0 +1,50 @@
+use font_index::{FontCache, FontId, FontIndex, FontLibrary, GenericFamily, SharedData};
+use std::path::{Path, PathBuf};
+
+fn find_font(dir: &Path) -> Option<PathBuf> {
+    for entry in std::fs::read_dir(dir).ok()? {
+        let path = entry.ok()?.path();
+        if path.is_dir() {
+            if let Some(font) = find_font(&path) {
+                return Some(font);
+            }
+        } else if let Some(ext) = path.extension() {
+            if ext == "ttf" || ext == "otf" {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn sandboxed_library_from_data_is_queryable() {
+    let path = match ["/usr/share/fonts/", "/System/Library/Fonts/", "C:\\Windows\\Fonts\\"]
+        .iter()
+        .find_map(|dir| find_font(Path::new(dir)))
+    {
+        Some(path) => path,
+        None => return,
+    };
+    let data = SharedData::new(std::fs::read(path).unwrap());
+    let library = FontLibrary::builder().sandbox(true).add_data(data).build();
+    assert!(library.is_sandboxed());
+    assert!(library.blocked_paths().next().is_none());
+    let mut cache = FontCache::new(&library);
+    let name = cache.font_by_id(FontId(0)).unwrap().family_name().to_string();
+    let id = cache.query(name.as_str(), ()).unwrap().id();
+    assert_eq!(id, FontId(0));
+    assert!(cache.get(id).is_some());
+}
+
+#[test]
+fn default_library_is_sandboxed_in_sandboxed_process() {
+    FontLibrary::builder().sandbox(true).build();
+    let library = FontLibrary::default();
+    assert!(library.is_sandboxed());
+    assert!(FontLibrary::global().is_sandboxed());
+    assert!(FontIndex::global().font_by_id(FontId(0)).is_none());
+    assert!(FontCache::default()
+        .query(GenericFamily::SansSerif, ())
+        .is_none());
+}